@@ -0,0 +1,81 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use log::debug;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::OneError;
+
+const CACHE_DIR: &str = "cache";
+
+#[derive(Serialize, Deserialize, Debug)]
+struct CacheEntry<T> {
+    fetched_at: u64,
+    data: T,
+}
+
+fn path_for(kind: &str, key_parts: &[&[String]]) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    for part in key_parts {
+        part.hash(&mut hasher);
+    }
+    PathBuf::from(CACHE_DIR).join(format!("{}-{:x}.json", kind, hasher.finish()))
+}
+
+/// Returns the cached payload for `kind`/`key_parts` if a cache file exists
+/// and is younger than `max_age`. Any miss, parse failure, or stale entry is
+/// treated the same way: fall through to a live fetch rather than erroring.
+/// Used both for provider responses (`kind` = provider name, `key_parts` =
+/// `[symbols, fiats]`) and for fiat cross-rates (`kind` = `"fxrates"`,
+/// `key_parts` = `[targets]`).
+pub fn load<T: DeserializeOwned>(
+    kind: &str,
+    key_parts: &[&[String]],
+    max_age: Duration,
+) -> Option<T> {
+    let path = path_for(kind, key_parts);
+    let contents = fs::read_to_string(&path).ok()?;
+    let entry: CacheEntry<T> = match serde_json::from_str(&contents) {
+        Ok(entry) => entry,
+        Err(err) => {
+            debug!("Ignoring unreadable cache file {:?}: {}", path, err);
+            return None;
+        }
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the Unix epoch")
+        .as_secs();
+    let age = Duration::from_secs(now.saturating_sub(entry.fetched_at));
+    if age > max_age {
+        debug!("Cache file {:?} is stale ({:?} old)", path, age);
+        return None;
+    }
+
+    Some(entry.data)
+}
+
+/// Overwrites the cache file for `kind`/`key_parts` with `data` stamped with
+/// the current time.
+pub fn store<T: Serialize>(
+    kind: &str,
+    key_parts: &[&[String]],
+    data: &T,
+) -> Result<(), OneError> {
+    fs::create_dir_all(CACHE_DIR)?;
+    let entry = CacheEntry {
+        fetched_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock is before the Unix epoch")
+            .as_secs(),
+        data,
+    };
+    let path = path_for(kind, key_parts);
+    fs::write(path, serde_json::to_vec(&entry).expect("CacheEntry always serializes"))?;
+    Ok(())
+}
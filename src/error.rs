@@ -0,0 +1,70 @@
+use core::fmt;
+
+#[derive(Debug)]
+pub enum OneError {
+    CSV(csv::Error),
+    IO(std::io::Error),
+    Reqwest(reqwest::Error),
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+    Proxy(Box<tokio_socks::Error>),
+    InvalidCurrency { symbol: String },
+    UnknownTransactionKind { raw: String },
+    UnsupportedProxy { proxy: String },
+    InvalidUserAgent { user_agent: String },
+}
+
+impl std::error::Error for OneError {}
+
+impl fmt::Display for OneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OneError::CSV(err) => write!(f, "Error while writing the CSV file {}", err),
+            OneError::IO(err) => write!(f, "Error while flushing the file {}", err),
+            OneError::Reqwest(err) => write!(f, "Error while fetching data {}", err),
+            OneError::WebSocket(err) => write!(f, "Error on the watch websocket {}", err),
+            OneError::Proxy(err) => write!(f, "Error connecting through the proxy {}", err),
+            OneError::InvalidCurrency { symbol } => {
+                write!(f, "'{}' is not a supported fiat currency", symbol)
+            }
+            OneError::UnknownTransactionKind { raw } => {
+                write!(f, "'{}' is not a recognized transaction type", raw)
+            }
+            OneError::UnsupportedProxy { proxy } => {
+                write!(f, "'{}' is not supported, watch only proxies through socks5://", proxy)
+            }
+            OneError::InvalidUserAgent { user_agent } => {
+                write!(f, "'{}' is not a valid User-Agent header value", user_agent)
+            }
+        }
+    }
+}
+
+impl From<reqwest::Error> for OneError {
+    fn from(err: reqwest::Error) -> OneError {
+        OneError::Reqwest(err)
+    }
+}
+
+impl From<csv::Error> for OneError {
+    fn from(err: csv::Error) -> OneError {
+        OneError::CSV(err)
+    }
+}
+
+impl From<std::io::Error> for OneError {
+    fn from(err: std::io::Error) -> OneError {
+        OneError::IO(err)
+    }
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for OneError {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> OneError {
+        OneError::WebSocket(Box::new(err))
+    }
+}
+
+impl From<tokio_socks::Error> for OneError {
+    fn from(err: tokio_socks::Error) -> OneError {
+        OneError::Proxy(Box::new(err))
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::env;
+
+use serde::Deserialize;
+
+use crate::error::OneError;
+use crate::types::Quote;
+
+/// Fiat currencies this tool knows how to price in. CoinMarketCap will
+/// happily quote directly in any of these via `convert=`; providers that
+/// only speak USD (CoinGecko) fall back to [`fetch_cross_rates`] instead.
+pub const SUPPORTED: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "AUD", "CAD", "CHF", "CNY", "INR", "BRL",
+];
+
+/// Rejects any requested `--convert` code we don't recognise, rather than
+/// panicking later on a missing `quote["XYZ"]` lookup.
+pub fn validate(fiats: &[String]) -> Result<(), OneError> {
+    for fiat in fiats {
+        if !SUPPORTED.contains(&fiat.as_str()) {
+            return Err(OneError::InvalidCurrency {
+                symbol: fiat.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[derive(Deserialize, Debug)]
+struct LiveRatesResponse {
+    quotes: HashMap<String, f64>,
+}
+
+/// Fetches USD -> `targets` cross rates from a currencylayer-style `live`
+/// endpoint, whose `quotes` map is keyed like `"USDEUR"`.
+pub async fn fetch_cross_rates(
+    client: &reqwest::Client,
+    targets: &[String],
+) -> Result<HashMap<String, f64>, OneError> {
+    let access_key =
+        env::var("CURRENCYLAYER_API_KEY").expect("CURRENCYLAYER_API_KEY key not set");
+    let currencies = targets.join(",");
+    let resp: LiveRatesResponse = client
+        .get("https://api.currencylayer.com/live")
+        .query(&[
+            ("access_key", access_key.as_str()),
+            ("source", "USD"),
+            ("currencies", currencies.as_str()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(targets
+        .iter()
+        .filter_map(|target| {
+            resp.quotes
+                .get(&format!("USD{}", target))
+                .map(|rate| (target.clone(), *rate))
+        })
+        .collect())
+}
+
+/// Derives a fiat quote from a USD quote using a USD -> fiat cross rate.
+pub fn convert_quote(usd: &Quote, rate: f64) -> Quote {
+    Quote {
+        price: usd.price * rate,
+        percent_change_7d: usd.percent_change_7d,
+        volume_24h: usd.volume_24h * rate,
+        market_cap: usd.market_cap * rate,
+    }
+}
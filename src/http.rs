@@ -0,0 +1,16 @@
+use reqwest::{Client, Proxy};
+
+use crate::error::OneError;
+
+/// Builds the shared HTTP client, optionally routed through a proxy (e.g.
+/// `socks5://127.0.0.1:9050` for Tor) and with a custom user agent.
+pub fn build_client(proxy: Option<&str>, user_agent: Option<&str>) -> Result<Client, OneError> {
+    let mut builder = Client::builder();
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(Proxy::all(proxy)?);
+    }
+    if let Some(user_agent) = user_agent {
+        builder = builder.user_agent(user_agent);
+    }
+    Ok(builder.build()?)
+}
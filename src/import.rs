@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use csv::{ReaderBuilder, Writer};
+use serde::{Deserialize, Deserializer};
+
+use crate::error::OneError;
+use crate::providers::{CoinGeckoProvider, CoinMarketCapProvider, Provider, RateProvider};
+use crate::types::Currency;
+
+/// What kind of ledger entry a [`Transaction`] represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionKind {
+    Deposit,
+    Withdrawal,
+    Trade,
+}
+
+/// A single normalized ledger entry, independent of which exchange export
+/// it came from.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    pub time: NaiveDateTime,
+    pub kind: TransactionKind,
+    pub coin: String,
+    pub amount: f64,
+}
+
+/// Row shape of an FTX-style transaction-history export.
+#[derive(Debug, Deserialize)]
+struct FtxRow {
+    #[serde(rename = "Time", deserialize_with = "deserialize_ftx_time")]
+    time: NaiveDateTime,
+    #[serde(rename = "Coin")]
+    coin: String,
+    #[serde(rename = "Amount")]
+    amount: f64,
+    #[serde(rename = "Type")]
+    kind: String,
+}
+
+/// FTX exports timestamps like `1/31/2021 3:45:00 PM`.
+fn deserialize_ftx_time<'de, D>(deserializer: D) -> Result<NaiveDateTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    NaiveDateTime::parse_from_str(&raw, "%m/%d/%Y %I:%M:%S %p").map_err(serde::de::Error::custom)
+}
+
+impl TryFrom<FtxRow> for Transaction {
+    type Error = OneError;
+
+    fn try_from(row: FtxRow) -> Result<Self, Self::Error> {
+        let kind = match row.kind.to_lowercase().as_str() {
+            "deposit" => TransactionKind::Deposit,
+            "withdrawal" => TransactionKind::Withdrawal,
+            "trade" | "buy" | "sell" => TransactionKind::Trade,
+            other => {
+                return Err(OneError::UnknownTransactionKind {
+                    raw: other.to_string(),
+                })
+            }
+        };
+        Ok(Transaction {
+            time: row.time,
+            kind,
+            coin: row.coin,
+            amount: row.amount,
+        })
+    }
+}
+
+/// Parses an FTX-style transaction-history CSV export into normalized
+/// [`Transaction`]s.
+pub fn parse_ftx_csv(path: &str) -> Result<Vec<Transaction>, OneError> {
+    let mut reader = ReaderBuilder::new().from_path(path)?;
+    let mut transactions = Vec::new();
+    for result in reader.deserialize() {
+        let row: FtxRow = result?;
+        transactions.push(Transaction::try_from(row)?);
+    }
+    transactions.sort_by_key(|transaction| transaction.time);
+    Ok(transactions)
+}
+
+/// Nets each coin's transactions down to a single held quantity. FTX exports
+/// don't agree on the sign of deposit/withdrawal amounts, so holdings are
+/// derived from `kind` rather than trusted from the raw `amount`: deposits
+/// always add, withdrawals always subtract, and trades keep the CSV's sign
+/// since it already encodes the side (buy vs. sell).
+pub fn aggregate_holdings(transactions: &[Transaction]) -> HashMap<String, f64> {
+    let mut holdings = HashMap::new();
+    for transaction in transactions {
+        let signed_amount = match transaction.kind {
+            TransactionKind::Deposit => transaction.amount.abs(),
+            TransactionKind::Withdrawal => -transaction.amount.abs(),
+            TransactionKind::Trade => transaction.amount,
+        };
+        *holdings.entry(transaction.coin.clone()).or_insert(0.0) += signed_amount;
+    }
+    holdings
+}
+
+/// Values `holdings` at the latest USD quote from `provider` and writes a
+/// `coin, quantity, unit_price, value_usd` summary to `out_path`.
+pub async fn write_valuation_report(
+    provider: Provider,
+    client: reqwest::Client,
+    holdings: &HashMap<String, f64>,
+    out_path: &str,
+) -> Result<(), OneError> {
+    let coins: Vec<String> = holdings.keys().cloned().collect();
+    let usd = vec!["USD".to_string()];
+
+    let quotes: Vec<Currency> = match provider {
+        Provider::CoinMarketCap => {
+            let api_key = std::env::var("CMS_API_KEY").expect("CMS_API_KEY key not set");
+            let provider = CoinMarketCapProvider::new(client, api_key);
+            provider
+                .latest_rates(&coins, &usd)
+                .await
+                .map_err(OneError::from)?
+        }
+        Provider::CoinGecko => {
+            let provider = CoinGeckoProvider::new(client);
+            provider
+                .latest_rates(&coins, &usd)
+                .await
+                .map_err(OneError::from)?
+        }
+    };
+
+    let prices: HashMap<String, f64> = quotes
+        .into_iter()
+        .filter_map(|currency| {
+            currency
+                .quote
+                .get("USD")
+                .map(|quote| (currency.symbol, quote.price))
+        })
+        .collect();
+
+    let mut wtr = Writer::from_path(out_path)?;
+    wtr.write_record(&["coin", "quantity", "unit_price", "value_usd"])?;
+    for (coin, quantity) in holdings {
+        let unit_price = prices.get(coin).copied().unwrap_or(0.0);
+        wtr.write_record(&[
+            coin.clone(),
+            quantity.to_string(),
+            unit_price.to_string(),
+            (quantity * unit_price).to_string(),
+        ])?;
+    }
+    wtr.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ftx_time() -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("1/31/2021 3:45:00 PM", "%m/%d/%Y %I:%M:%S %p").unwrap()
+    }
+
+    fn transaction(kind: TransactionKind, amount: f64) -> Transaction {
+        Transaction {
+            time: ftx_time(),
+            kind,
+            coin: "BTC".to_string(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn aggregate_holdings_adds_deposits_regardless_of_csv_sign() {
+        let holdings = aggregate_holdings(&[transaction(TransactionKind::Deposit, -1.0)]);
+        assert_eq!(holdings["BTC"], 1.0);
+    }
+
+    #[test]
+    fn aggregate_holdings_subtracts_withdrawals_regardless_of_csv_sign() {
+        let holdings = aggregate_holdings(&[transaction(TransactionKind::Withdrawal, 1.0)]);
+        assert_eq!(holdings["BTC"], -1.0);
+    }
+
+    #[test]
+    fn aggregate_holdings_trusts_the_csv_sign_for_trades() {
+        let holdings = aggregate_holdings(&[
+            transaction(TransactionKind::Trade, -0.5),
+            transaction(TransactionKind::Trade, 2.0),
+        ]);
+        assert_eq!(holdings["BTC"], 1.5);
+    }
+
+    #[test]
+    fn try_from_ftx_row_maps_buy_and_sell_to_trade() {
+        let row = FtxRow {
+            time: ftx_time(),
+            coin: "ETH".to_string(),
+            amount: 1.0,
+            kind: "Buy".to_string(),
+        };
+        let transaction = Transaction::try_from(row).unwrap();
+        assert_eq!(transaction.kind, TransactionKind::Trade);
+    }
+
+    #[test]
+    fn try_from_ftx_row_rejects_an_unrecognized_kind() {
+        let row = FtxRow {
+            time: ftx_time(),
+            coin: "ETH".to_string(),
+            amount: 1.0,
+            kind: "airdrop".to_string(),
+        };
+        assert!(Transaction::try_from(row).is_err());
+    }
+
+    #[test]
+    fn deserialize_ftx_time_parses_the_export_format() {
+        let mut reader = ReaderBuilder::new().from_reader(
+            "Time,Coin,Amount,Type\n1/31/2021 3:45:00 PM,BTC,0.5,deposit\n".as_bytes(),
+        );
+        let row: FtxRow = reader.deserialize().next().unwrap().unwrap();
+        assert_eq!(row.time, ftx_time());
+    }
+}
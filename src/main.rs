@@ -1,72 +1,24 @@
-use core::fmt;
+mod cache;
+mod error;
+mod fiat;
+mod http;
+mod import;
+mod providers;
+mod types;
+mod watch;
+
 use std::collections::HashMap;
-use serde::{Deserialize};
 use std::env;
-use clap::{Arg, App};
-use csv::Writer;
-use log::{info, debug};
-use log4rs;
-
-#[derive(Deserialize, Debug)]
-struct Quote {
-    price: f64,
-    percent_change_7d: f64,
-    volume_24h: f64,
-    market_cap: f64,
-}
-
-#[derive(Deserialize, Debug)]
-struct Currency {
-    id: i32,
-    name: String,
-    symbol: String,
-    slug: String,
-    quote: HashMap<String, Quote>,
-}
-
-
-#[derive(Deserialize, Debug)]
-struct CMCResponse {
-    data: HashMap<String, Currency>,
-}
-
-#[derive(Debug)]
-enum OneError {
-    CSV(csv::Error),
-    IO(std::io::Error),
-    Reqwest(reqwest::Error),
-}
-
-impl std::error::Error for OneError {}
-
-impl fmt::Display for OneError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            OneError::CSV(err) => write!(f, "Error while writing the CSV file {}", err),
-            OneError::IO(err) => write!(f, "Error while flushing the file {}", err),
-            OneError::Reqwest(err) => write!(f, "Error while fetching data {}", err),
-        }
-    }
-}
-
-impl From<reqwest::Error> for OneError {
-    fn from(err: reqwest::Error) -> OneError {
-        OneError::Reqwest(err)
-    }
-}
-
-impl From<csv::Error> for OneError {
-    fn from(err: csv::Error) -> OneError {
-        OneError::CSV(err)
-    }
-}
+use std::str::FromStr;
+use std::time::Duration;
 
-impl From<std::io::Error> for OneError {
-    fn from(err: std::io::Error) -> OneError {
-        OneError::IO(err)
-    }
-}
+use clap::{App, AppSettings, Arg, SubCommand};
+use csv::Writer;
+use log::{debug, info};
 
+use error::OneError;
+use providers::{CoinGeckoProvider, CoinMarketCapProvider, Provider, RateProvider};
+use types::Currency;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -77,47 +29,251 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .version("1.0")
         .author("Vlad Lytvynenko. <sir.sagramor@gmail.com>")
         .about("Gets prices of given cryptocurrencies")
-        .arg(Arg::with_name("currencies")
-            .long("currencies")
-            .min_values(1)
-            .required(true))
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .arg(
+            Arg::with_name("currencies")
+                .long("currencies")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("provider")
+                .long("provider")
+                .possible_values(Provider::VARIANTS)
+                .default_value("coinmarketcap"),
+        )
+        .arg(
+            Arg::with_name("convert")
+                .long("convert")
+                .min_values(1)
+                .default_value("USD"),
+        )
+        .arg(
+            Arg::with_name("max-age")
+                .long("max-age")
+                .takes_value(true)
+                .default_value("0")
+                .help("Reuse a cached response younger than this many seconds instead of fetching"),
+        )
+        .arg(
+            Arg::with_name("proxy")
+                .long("proxy")
+                .takes_value(true)
+                .help("Route HTTP requests through this proxy, e.g. socks5://127.0.0.1:9050"),
+        )
+        .arg(
+            Arg::with_name("user-agent")
+                .long("user-agent")
+                .takes_value(true),
+        )
+        .subcommand(
+            SubCommand::with_name("watch")
+                .about("Stream live ticker updates from Kraken instead of a one-shot fetch")
+                .arg(
+                    Arg::with_name("currencies")
+                        .long("currencies")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("proxy")
+                        .long("proxy")
+                        .takes_value(true)
+                        .help("Route the Kraken websocket through this socks5:// proxy"),
+                )
+                .arg(
+                    Arg::with_name("user-agent")
+                        .long("user-agent")
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("import")
+                .about("Import an exchange transaction-history CSV and value the resulting portfolio")
+                .arg(
+                    Arg::with_name("file")
+                        .long("file")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("provider")
+                        .long("provider")
+                        .possible_values(Provider::VARIANTS)
+                        .default_value("coinmarketcap"),
+                )
+                .arg(
+                    Arg::with_name("proxy")
+                        .long("proxy")
+                        .takes_value(true)
+                        .help("Route HTTP requests through this proxy, e.g. socks5://127.0.0.1:9050"),
+                )
+                .arg(
+                    Arg::with_name("user-agent")
+                        .long("user-agent")
+                        .takes_value(true),
+                ),
+        )
         .get_matches();
 
+    if let Some(watch_matches) = matches.subcommand_matches("watch") {
+        let currencies = watch_matches
+            .value_of("currencies")
+            .expect("There are no currencies were passes");
+        let symbols: Vec<String> = currencies.split(',').map(str::to_string).collect();
+        return Ok(watch::run(
+            &symbols,
+            "out.csv",
+            watch_matches.value_of("proxy"),
+            watch_matches.value_of("user-agent"),
+        )
+        .await?);
+    }
+
+    if let Some(import_matches) = matches.subcommand_matches("import") {
+        let file = import_matches
+            .value_of("file")
+            .expect("--file is required");
+        let provider = Provider::from_str(
+            import_matches
+                .value_of("provider")
+                .expect("--provider has a default value"),
+        )
+        .expect("clap already validated --provider against Provider::VARIANTS");
+
+        let client = http::build_client(
+            import_matches.value_of("proxy"),
+            import_matches.value_of("user-agent"),
+        )?;
+
+        let transactions = import::parse_ftx_csv(file)?;
+        let holdings = import::aggregate_holdings(&transactions);
+        import::write_valuation_report(provider, client, &holdings, "portfolio.csv").await?;
+        info!("Imported {} and wrote portfolio.csv", file);
+        return Ok(());
+    }
+
     let currencies = matches
         .value_of("currencies")
         .expect("There are no currencies were passes");
+    let symbols: Vec<String> = currencies.split(',').map(str::to_string).collect();
+
+    let fiats: Vec<String> = matches
+        .values_of("convert")
+        .expect("--convert has a default value")
+        .map(str::to_uppercase)
+        .collect();
+    fiat::validate(&fiats)?;
+
+    let provider = Provider::from_str(
+        matches
+            .value_of("provider")
+            .expect("--provider has a default value"),
+    )
+    .expect("clap already validated --provider against Provider::VARIANTS");
+
+    let max_age = Duration::from_secs(
+        matches
+            .value_of("max-age")
+            .expect("--max-age has a default value")
+            .parse()
+            .expect("--max-age must be a number of seconds"),
+    );
 
     debug!("Querying the following currencies: {:?}", currencies);
 
-    let client = reqwest::Client::new();
-    let api_key = env::var("CMS_API_KEY").expect("CMS_API_KEY key not set");
-    let resp = client
-        .get("https://pro-api.coinmarketcap.com/v1/cryptocurrency/quotes/latest")
-        .header("X-CMC_PRO_API_KEY", api_key)
-        .query(&[("symbol", currencies.to_string())])
-        .send()
-        .await?;
-
-    let status = resp.status();
-    match status {
-        reqwest::StatusCode::OK => {
-            let resp: CMCResponse = resp.json().await?;
-            let mut wtr = Writer::from_path("out.csv")?;
-            wtr.write_record(&["name", "symbol", "price", "percent_change_7d"])?;
-            for currency in resp.data.values() {
-                wtr.write_record(&[
-                    &currency.name,
-                    &currency.symbol,
-                    &currency.quote["USD"].price.to_string(),
-                    &currency.quote["USD"].percent_change_7d.to_string()]
-                )?;
+    let client = http::build_client(matches.value_of("proxy"), matches.value_of("user-agent"))?;
+
+    let mut results: Vec<Currency> =
+        match cache::load(provider.as_str(), &[&symbols, &fiats], max_age) {
+            Some(cached) => {
+                debug!("Reusing cached {} response", provider.as_str());
+                cached
+            }
+            None => {
+                let fetched = match provider {
+                    Provider::CoinMarketCap => {
+                        let api_key = env::var("CMS_API_KEY").expect("CMS_API_KEY key not set");
+                        let provider = CoinMarketCapProvider::new(client.clone(), api_key);
+                        provider
+                            .latest_rates(&symbols, &fiats)
+                            .await
+                            .map_err(OneError::from)?
+                    }
+                    Provider::CoinGecko => {
+                        let provider = CoinGeckoProvider::new(client.clone());
+                        provider
+                            .latest_rates(&symbols, &fiats)
+                            .await
+                            .map_err(OneError::from)?
+                    }
+                };
+                cache::store(provider.as_str(), &[&symbols, &fiats], &fetched)?;
+                fetched
+            }
+        };
+
+    let missing_fiats: Vec<String> = fiats
+        .iter()
+        .filter(|fiat| !results.iter().all(|currency| currency.quote.contains_key(*fiat)))
+        .cloned()
+        .collect();
+    if !missing_fiats.is_empty() {
+        let rates: HashMap<String, f64> =
+            match cache::load("fxrates", &[&missing_fiats], max_age) {
+                Some(cached) => {
+                    debug!("Reusing cached fx cross-rates for {:?}", missing_fiats);
+                    cached
+                }
+                None => {
+                    debug!(
+                        "Provider only returned USD, cross-converting into {:?}",
+                        missing_fiats
+                    );
+                    let fetched = fiat::fetch_cross_rates(&client, &missing_fiats).await?;
+                    cache::store("fxrates", &[&missing_fiats], &fetched)?;
+                    fetched
+                }
+            };
+        for currency in &mut results {
+            let Some(usd) = currency.quote.get("USD").cloned() else {
+                continue;
+            };
+            for target in &missing_fiats {
+                if let Some(rate) = rates.get(target) {
+                    currency
+                        .quote
+                        .insert(target.clone(), fiat::convert_quote(&usd, *rate));
+                }
             }
-            wtr.flush()?;
         }
-        _ => {
-            info!("Status: {}\nResponse Body: {}", status, resp.text().await?);
+    }
+
+    let mut header = vec!["name".to_string(), "symbol".to_string()];
+    for fiat in &fiats {
+        header.push(format!("price_{}", fiat));
+        header.push(format!("percent_change_7d_{}", fiat));
+    }
+
+    let mut wtr = Writer::from_path("out.csv")?;
+    wtr.write_record(&header)?;
+    for currency in &results {
+        let mut row = vec![currency.name.clone(), currency.symbol.clone()];
+        for fiat in &fiats {
+            match currency.quote.get(fiat) {
+                Some(quote) => {
+                    row.push(quote.price.to_string());
+                    row.push(quote.percent_change_7d.to_string());
+                }
+                None => {
+                    row.push(String::new());
+                    row.push(String::new());
+                }
+            }
         }
+        wtr.write_record(&row)?;
     }
+    wtr.flush()?;
 
     info!("Queried {} and wrote CSV file", currencies);
 
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::types::{Currency, Quote};
+
+use super::RateProvider;
+
+#[derive(Deserialize, Debug)]
+struct CoinGeckoMarket {
+    id: String,
+    symbol: String,
+    name: String,
+    current_price: f64,
+    #[serde(default)]
+    price_change_percentage_7d_in_currency: f64,
+    total_volume: f64,
+    market_cap: f64,
+}
+
+/// Talks to the free CoinGecko `/coins/markets` endpoint. Unlike
+/// CoinMarketCap this needs no API key, at the cost of coarser rate limits.
+pub struct CoinGeckoProvider {
+    client: reqwest::Client,
+    vs_currency: String,
+}
+
+impl CoinGeckoProvider {
+    pub fn new(client: reqwest::Client) -> Self {
+        CoinGeckoProvider {
+            client,
+            vs_currency: "usd".to_string(),
+        }
+    }
+}
+
+impl RateProvider for CoinGeckoProvider {
+    type Error = reqwest::Error;
+
+    async fn latest_rates(
+        &self,
+        symbols: &[String],
+        // CoinGecko's markets endpoint only takes a single `vs_currency`; we
+        // always quote in USD and let the caller cross-convert into any
+        // other requested fiats.
+        _fiats: &[String],
+    ) -> Result<Vec<Currency>, Self::Error> {
+        let symbols = symbols.join(",").to_lowercase();
+        let markets: Vec<CoinGeckoMarket> = self
+            .client
+            .get("https://api.coingecko.com/api/v3/coins/markets")
+            .query(&[
+                ("vs_currency", self.vs_currency.as_str()),
+                ("symbols", symbols.as_str()),
+                ("price_change_percentage", "7d"),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(markets
+            .into_iter()
+            .map(|market| {
+                let mut quote = HashMap::with_capacity(1);
+                quote.insert(
+                    self.vs_currency.to_uppercase(),
+                    Quote {
+                        price: market.current_price,
+                        percent_change_7d: market.price_change_percentage_7d_in_currency,
+                        volume_24h: market.total_volume,
+                        market_cap: market.market_cap,
+                    },
+                );
+                Currency {
+                    id: market.id.clone(),
+                    name: market.name,
+                    symbol: market.symbol.to_uppercase(),
+                    slug: market.id,
+                    quote,
+                }
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::types::{Currency, Quote};
+
+use super::RateProvider;
+
+#[derive(Deserialize, Debug)]
+struct CmcQuote {
+    price: f64,
+    percent_change_7d: f64,
+    volume_24h: f64,
+    market_cap: f64,
+}
+
+#[derive(Deserialize, Debug)]
+struct CmcCurrency {
+    id: i32,
+    name: String,
+    symbol: String,
+    slug: String,
+    quote: HashMap<String, CmcQuote>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CmcResponse {
+    data: HashMap<String, CmcCurrency>,
+}
+
+impl From<CmcCurrency> for Currency {
+    fn from(currency: CmcCurrency) -> Self {
+        Currency {
+            id: currency.id.to_string(),
+            name: currency.name,
+            symbol: currency.symbol,
+            slug: currency.slug,
+            quote: currency
+                .quote
+                .into_iter()
+                .map(|(fiat, quote)| {
+                    (
+                        fiat,
+                        Quote {
+                            price: quote.price,
+                            percent_change_7d: quote.percent_change_7d,
+                            volume_24h: quote.volume_24h,
+                            market_cap: quote.market_cap,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Talks to the CoinMarketCap Pro API. Requires `CMS_API_KEY` to be set.
+pub struct CoinMarketCapProvider {
+    client: reqwest::Client,
+    api_key: String,
+}
+
+impl CoinMarketCapProvider {
+    pub fn new(client: reqwest::Client, api_key: String) -> Self {
+        CoinMarketCapProvider { client, api_key }
+    }
+}
+
+impl RateProvider for CoinMarketCapProvider {
+    type Error = reqwest::Error;
+
+    async fn latest_rates(
+        &self,
+        symbols: &[String],
+        fiats: &[String],
+    ) -> Result<Vec<Currency>, Self::Error> {
+        let resp: CmcResponse = self
+            .client
+            .get("https://pro-api.coinmarketcap.com/v1/cryptocurrency/quotes/latest")
+            .header("X-CMC_PRO_API_KEY", &self.api_key)
+            .query(&[
+                ("symbol", symbols.join(",")),
+                ("convert", fiats.join(",")),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(resp.data.into_values().map(Currency::from).collect())
+    }
+}
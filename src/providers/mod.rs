@@ -0,0 +1,53 @@
+mod coingecko;
+mod coinmarketcap;
+
+use std::str::FromStr;
+
+pub use coingecko::CoinGeckoProvider;
+pub use coinmarketcap::CoinMarketCapProvider;
+
+use crate::types::Currency;
+
+/// Common interface for anything that can look up the latest price of a
+/// set of symbols. Each backend owns its own wire format and maps it into
+/// the shared [`Currency`]/[`Quote`](crate::types::Quote) types, so callers
+/// never have to special-case a particular exchange.
+pub trait RateProvider {
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    async fn latest_rates(
+        &self,
+        symbols: &[String],
+        fiats: &[String],
+    ) -> Result<Vec<Currency>, Self::Error>;
+}
+
+/// The set of backends selectable via `--provider`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    CoinMarketCap,
+    CoinGecko,
+}
+
+impl Provider {
+    pub const VARIANTS: &'static [&'static str] = &["coinmarketcap", "coingecko"];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Provider::CoinMarketCap => "coinmarketcap",
+            Provider::CoinGecko => "coingecko",
+        }
+    }
+}
+
+impl FromStr for Provider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "coinmarketcap" => Ok(Provider::CoinMarketCap),
+            "coingecko" => Ok(Provider::CoinGecko),
+            other => Err(format!("unknown provider '{}'", other)),
+        }
+    }
+}
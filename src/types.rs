@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A single fiat-denominated quote for a currency, keyed by fiat symbol
+/// (e.g. `"USD"`) in [`Currency::quote`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Quote {
+    pub price: f64,
+    pub percent_change_7d: f64,
+    pub volume_24h: f64,
+    pub market_cap: f64,
+}
+
+/// Provider-agnostic view of a priced currency. Every [`RateProvider`](crate::providers::RateProvider)
+/// backend maps its own wire format into this shape so the CSV-writing code
+/// never has to know which exchange the data came from.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Currency {
+    pub id: String,
+    pub name: String,
+    pub symbol: String,
+    pub slug: String,
+    pub quote: HashMap<String, Quote>,
+}
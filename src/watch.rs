@@ -0,0 +1,160 @@
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use csv::WriterBuilder;
+use futures_util::{SinkExt, StreamExt};
+use http::header::USER_AGENT;
+use http::HeaderValue;
+use log::{debug, info};
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio_socks::tcp::Socks5Stream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::client::Request;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{client_async_tls, connect_async, MaybeTlsStream, WebSocketStream};
+
+use crate::error::OneError;
+
+const KRAKEN_HOST: &str = "ws.kraken.com";
+const KRAKEN_WS_URL: &str = "wss://ws.kraken.com";
+const KRAKEN_PORT: u16 = 443;
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Keeps a Kraken ticker subscription open for `symbols` and appends a row
+/// to `out_path` on every tick, reconnecting with exponential backoff if the
+/// socket drops. `proxy`, if given, must be a `socks5://host:port` URL.
+pub async fn run(
+    symbols: &[String],
+    out_path: &str,
+    proxy: Option<&str>,
+    user_agent: Option<&str>,
+) -> Result<(), OneError> {
+    let pairs: Vec<String> = symbols.iter().map(|s| format!("{}/USD", s)).collect();
+    let mut backoff = Duration::from_secs(1);
+
+    loop {
+        if let Err(err) = stream_once(&pairs, out_path, proxy, user_agent).await {
+            info!(
+                "Kraken websocket dropped ({}), reconnecting in {:?}",
+                err, backoff
+            );
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            continue;
+        }
+        backoff = Duration::from_secs(1);
+    }
+}
+
+async fn stream_once(
+    pairs: &[String],
+    out_path: &str,
+    proxy: Option<&str>,
+    user_agent: Option<&str>,
+) -> Result<(), OneError> {
+    let mut socket = connect(proxy, user_agent).await?;
+
+    let subscribe = json!({
+        "event": "subscribe",
+        "pair": pairs,
+        "subscription": { "name": "ticker" },
+    });
+    socket.send(Message::Text(subscribe.to_string())).await?;
+
+    let is_new_file = !Path::new(out_path).exists();
+    let mut wtr = WriterBuilder::new().from_writer(
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(out_path)?,
+    );
+    if is_new_file {
+        wtr.write_record(&["timestamp", "pair", "price", "volume"])?;
+        wtr.flush()?;
+    }
+
+    while let Some(msg) = socket.next().await {
+        let text = match msg? {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+
+        let value: Value = match serde_json::from_str(&text) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+
+        // Control frames such as {"event":"heartbeat"} or {"event":"systemStatus",...}
+        // arrive as JSON objects; only array payloads carry ticker data.
+        if value.is_object() {
+            debug!("Ignoring Kraken control message: {}", text);
+            continue;
+        }
+
+        if let Some((pair, price, volume)) = parse_ticker(&value) {
+            wtr.write_record(&[current_timestamp(), pair, price, volume])?;
+            wtr.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Connects to the Kraken ticker endpoint, tunneling through `proxy` (a
+/// `socks5://host:port` URL) when given.
+async fn connect(
+    proxy: Option<&str>,
+    user_agent: Option<&str>,
+) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>, OneError> {
+    let request = build_request(user_agent)?;
+
+    let Some(proxy) = proxy else {
+        let (socket, _) = connect_async(request).await?;
+        return Ok(socket);
+    };
+
+    let proxy_addr = proxy
+        .strip_prefix("socks5://")
+        .ok_or_else(|| OneError::UnsupportedProxy {
+            proxy: proxy.to_string(),
+        })?;
+    let tcp = Socks5Stream::connect(proxy_addr, (KRAKEN_HOST, KRAKEN_PORT))
+        .await
+        .map_err(OneError::from)?
+        .into_inner();
+    let (socket, _) = client_async_tls(request, tcp).await?;
+    Ok(socket)
+}
+
+fn build_request(user_agent: Option<&str>) -> Result<Request, OneError> {
+    let mut request = KRAKEN_WS_URL.into_client_request()?;
+    if let Some(user_agent) = user_agent {
+        request.headers_mut().insert(
+            USER_AGENT,
+            HeaderValue::from_str(user_agent).map_err(|_| OneError::InvalidUserAgent {
+                user_agent: user_agent.to_string(),
+            })?,
+        );
+    }
+    Ok(request)
+}
+
+fn parse_ticker(value: &Value) -> Option<(String, String, String)> {
+    let array = value.as_array()?;
+    let pair = array.get(3)?.as_str()?.to_string();
+    let c = array.get(1)?.get("c")?.as_array()?;
+    let price = c.first()?.as_str()?.to_string();
+    let volume = c.get(1)?.as_str()?.to_string();
+    Some((pair, price, volume))
+}
+
+fn current_timestamp() -> String {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the Unix epoch")
+        .as_secs()
+        .to_string()
+}